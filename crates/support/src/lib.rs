@@ -0,0 +1,52 @@
+pub mod merge_locales;
+
+use std::path::Path;
+
+/// Get the lowercase file extension, or an empty string when there is none
+pub fn get_extension<P: AsRef<Path>>(path: P) -> String {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Read a file into a string, panicking with the path on failure
+pub fn open_file_to_string<P: AsRef<Path>>(path: P) -> String {
+    std::fs::read_to_string(path.as_ref())
+        .unwrap_or_else(|_| panic!("Unable to open file {}", path.as_ref().to_str().unwrap()))
+}
+
+/// Parse a locale file's content into a `serde_json::Value` according to its extension
+pub fn parse_string_to_serde_json(content: &str, ext: &str) -> Result<serde_json::Value, String> {
+    match ext {
+        "json" => serde_json::from_str(content).map_err(|e| e.to_string()),
+        "json5" => json5::from_str(content).map_err(|e| e.to_string()),
+        "yml" | "yaml" => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        "toml" => toml::from_str(content).map_err(|e| e.to_string()),
+        _ => Err(format!("Unsupported extension: {}", ext)),
+    }
+}
+
+/// Recursively merge `b` into `a`. Objects recurse key by key, arrays are
+/// unioned (duplicate elements removed, by full value equality), and
+/// scalars fall back to `b` winning on conflict.
+pub fn merge_value(a: &mut serde_json::Value, b: &serde_json::Value) {
+    match (a, b) {
+        (serde_json::Value::Object(a), serde_json::Value::Object(b)) => {
+            for (k, v) in b {
+                merge_value(a.entry(k.clone()).or_insert(serde_json::Value::Null), v);
+            }
+        }
+        (serde_json::Value::Array(a), serde_json::Value::Array(b)) => {
+            for v in b {
+                if !a.contains(v) {
+                    a.push(v.clone());
+                }
+            }
+        }
+        (a, b) => {
+            *a = b.clone();
+        }
+    }
+}
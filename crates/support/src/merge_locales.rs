@@ -3,53 +3,250 @@ use std::path::{Path, PathBuf};
 
 use crate::{get_extension, merge_value, open_file_to_string, parse_string_to_serde_json};
 
-/// Merge all translated files together
-pub fn get_merged_string<S: AsRef<Path>>(files: &[S]) -> serde_json::Value {
-    let all_translated_files = open_locales_files(&get_locales_files_path(files));
+/// File extensions that the merge tool knows how to read and write
+const ALL_SUPPORT_EXT: &[&str] = &["yml", "yaml", "json", "json5", "toml"];
+
+/// Whether `pattern` contains glob wildcards rather than naming a single file
+fn is_glob_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Expand a single `{a,b,c}` brace-alternation group in `pattern`, the way
+/// shells do. The `glob` crate doesn't understand brace alternation on its
+/// own and treats `{`/`}` as literal characters, so a pattern like
+/// `*.{yml,json,toml}` is expanded here into `*.yml`, `*.json`, `*.toml`
+/// before being handed to `glob::glob`. Patterns without a brace group are
+/// returned unchanged.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    match (pattern.find('{'), pattern.find('}')) {
+        (Some(start), Some(end)) if start < end => {
+            let prefix = &pattern[..start];
+            let suffix = &pattern[end + 1..];
+            pattern[start + 1..end]
+                .split(',')
+                .map(|alt| format!("{}{}{}", prefix, alt, suffix))
+                .collect()
+        }
+        _ => vec![pattern.to_string()],
+    }
+}
+
+/// Expand a glob pattern (e.g. `locales/**/*.yml`, or a brace-alternation
+/// pattern like `locales/**/*.{yml,json,toml}`) into the list of matching
+/// locale files, dropping any whose extension isn't in `ALL_SUPPORT_EXT`.
+fn expand_glob_files(pattern: &str) -> Vec<PathBuf> {
+    expand_braces(pattern)
+        .iter()
+        .flat_map(|p| {
+            glob::glob(p)
+                .map(|paths| paths.filter_map(Result::ok).collect::<Vec<_>>())
+                .unwrap_or_default()
+        })
+        .filter(|path| ALL_SUPPORT_EXT.iter().any(|ext| get_extension(path).eq(ext)))
+        .collect()
+}
+
+/// A conflicting scalar value found while merging in `strict` mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeError {
+    /// JSON pointer path to the offending key, e.g. `/一二三四/en`
+    pub pointer: String,
+    /// The value already merged in at `pointer`
+    pub previous: serde_json::Value,
+    /// The conflicting value found in the file being merged
+    pub conflicting: serde_json::Value,
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "conflicting translations at `{}`: `{}` vs `{}`",
+            self.pointer, self.previous, self.conflicting
+        )
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Recursively merge `b` into `a`, erroring on the first leaf key where both
+/// sides set a different scalar value. Identical scalars and nested objects
+/// merge without conflict, and arrays are unioned just like [`merge_value`].
+fn merge_value_strict(
+    a: &mut serde_json::Value,
+    b: &serde_json::Value,
+    pointer: &mut String,
+) -> Result<(), MergeError> {
+    match (a, b) {
+        (serde_json::Value::Object(a), serde_json::Value::Object(b)) => {
+            for (k, v) in b {
+                let entry = a.entry(k.clone()).or_insert(serde_json::Value::Null);
+                let depth = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&k.replace('~', "~0").replace('/', "~1"));
+                merge_value_strict(entry, v, pointer)?;
+                pointer.truncate(depth);
+            }
+            Ok(())
+        }
+        (serde_json::Value::Array(a), serde_json::Value::Array(b)) => {
+            for v in b {
+                if !a.contains(v) {
+                    a.push(v.clone());
+                }
+            }
+            Ok(())
+        }
+        (a, b) if *a == serde_json::Value::Null || a == b => {
+            *a = b.clone();
+            Ok(())
+        }
+        (a, b) => Err(MergeError {
+            pointer: pointer.clone(),
+            previous: a.clone(),
+            conflicting: b.clone(),
+        }),
+    }
+}
+
+/// Read a `--fromfile` manifest: one locale file path per line, blank lines
+/// ignored.
+fn read_fromfile_manifest<P: AsRef<Path>>(manifest: P) -> Vec<PathBuf> {
+    open_file_to_string(manifest)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Merge all translated files together. In `strict` mode, two files that set
+/// the same leaf key to different scalar values return a `MergeError`
+/// instead of silently letting the last file win. `fromfile`, if given, is a
+/// newline-delimited manifest of additional locale files to merge in.
+///
+/// Returns the merged value alongside every input path actually read, so
+/// callers can emit a `--depfile` for build systems.
+pub fn get_merged_string<S: AsRef<Path>>(
+    files: &[S],
+    strict: bool,
+    fromfile: Option<&Path>,
+) -> Result<(serde_json::Value, Vec<PathBuf>), MergeError> {
+    let mut all_files = get_locales_files_path(files);
+    if let Some(manifest) = fromfile {
+        all_files.extend(read_fromfile_manifest(manifest));
+    }
+
+    let all_translated_files = open_locales_files(&all_files);
     let mut all_merged_value = serde_json::Value::default();
 
-    for (content, path) in all_translated_files {
-        let ext = get_extension(path);
-        if let Ok(tmp) = parse_string_to_serde_json(&content, &ext) {
-            merge_value(&mut all_merged_value, &tmp);
+    for (content, _path, ext) in &all_translated_files {
+        if let Ok(tmp) = parse_string_to_serde_json(content, ext) {
+            if strict {
+                merge_value_strict(&mut all_merged_value, &tmp, &mut String::new())?;
+            } else {
+                merge_value(&mut all_merged_value, &tmp);
+            }
         }
     }
 
-    all_merged_value
+    let read_paths = all_translated_files
+        .into_iter()
+        .map(|(_, path, _)| path)
+        .collect();
+    Ok((all_merged_value, read_paths))
+}
+
+/// Write a Make-style depfile so build systems can re-run the merge only
+/// when a source locale changes, e.g. `locales/en.yml: locales/a.yml locales/b.yml`.
+pub fn write_depfile<P: AsRef<Path>>(depfile: P, target: &Path, inputs: &[PathBuf]) {
+    let mut file = std::fs::File::create(depfile.as_ref())
+        .unwrap_or_else(|_| panic!("Unable to create file {}.", depfile.as_ref().to_str().unwrap()));
+
+    let inputs = inputs
+        .iter()
+        .map(|p| p.to_str().unwrap())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    writeln!(&mut file, "{}: {}", target.to_str().unwrap(), inputs)
+        .unwrap_or_else(|_| panic!("Unable to create file {}.", depfile.as_ref().to_str().unwrap()));
 }
 
-/// Open all translated files to string with path
-fn open_locales_files(entry: &[PathBuf]) -> Vec<(String, PathBuf)> {
+/// Open all translated files to string with path, resolving each file's
+/// format from its extension or, when that's missing or unrecognized, by
+/// sniffing its content with [`detect_format`].
+fn open_locales_files(entry: &[PathBuf]) -> Vec<(String, PathBuf, String)> {
     entry
         .iter()
-        .map(|path| (open_file_to_string(path), path.clone()))
+        .map(|path| {
+            let content = open_file_to_string(path);
+            let ext = get_extension(path);
+            let ext = if ALL_SUPPORT_EXT.contains(&ext.as_str()) {
+                ext
+            } else {
+                detect_format(&content).unwrap_or(ext)
+            };
+            (content, path.clone(), ext)
+        })
         .collect::<Vec<_>>()
 }
 
+/// Sniff a locale file's format from its content when the extension is
+/// missing or unrecognized, trying JSON, then TOML, then YAML in turn. Only
+/// a parse that yields a top-level mapping is accepted, since locale files
+/// are always keyed objects: otherwise a flat TOML line like `en = "hello"`
+/// would also parse as a bare YAML scalar string and corrupt the merge.
+fn detect_format(content: &str) -> Option<String> {
+    if matches!(
+        serde_json::from_str::<serde_json::Value>(content),
+        Ok(serde_json::Value::Object(_))
+    ) {
+        return Some("json".to_string());
+    }
+    if matches!(toml::from_str::<toml::Value>(content), Ok(toml::Value::Table(_))) {
+        return Some("toml".to_string());
+    }
+    if matches!(
+        serde_yaml::from_str::<serde_yaml::Value>(content),
+        Ok(serde_yaml::Value::Mapping(_))
+    ) {
+        return Some("yaml".to_string());
+    }
+    None
+}
+
 /// Format input parameters as available paths
 fn get_locales_files_path<S: AsRef<Path>>(files: &[S]) -> Vec<PathBuf> {
-    let all_support_ext = ["yml", "yaml", "json", "toml"];
+    let mut all_files: Vec<PathBuf> = Vec::new();
+
+    if files.is_empty() {
+        // Nothing to expand; the caller may still supply files via `--fromfile`.
+        return all_files;
+    }
+
+    if files.len() == 1 && is_glob_pattern(files[0].as_ref().to_str().unwrap_or_default()) {
+        // A single glob pattern, e.g. `cargo i18n -m "locales/**/*.yml"`
+        return expand_glob_files(files[0].as_ref().to_str().unwrap());
+    }
+
     let file_path = Path::new(files.first().unwrap().as_ref());
     let ext = get_extension(file_path);
-    let mut all_files: Vec<PathBuf> = Vec::new();
 
     if files.len() >= 2 {
         //
         // All supported formats are available as parameters
         // Example `cargo i18n -m 1.yml 2.yaml 3.json 4.toml`
+        // Files with a missing or unrecognized extension are kept too;
+        // `open_locales_files` falls back to content sniffing for those.
         files.iter().for_each(|p| {
-            let path = Path::new(p.as_ref());
-            let ext = get_extension(path);
-
-            if all_support_ext.iter().any(|e| ext.eq(e)) {
-                all_files.push(path.into());
-            }
+            all_files.push(Path::new(p.as_ref()).into());
         });
     } else {
         let file_parent_path = file_path.parent().unwrap();
 
         // If only one parameter, The `TODO` file will be default.
-        all_support_ext.iter().for_each(|ex| {
+        ALL_SUPPORT_EXT.iter().for_each(|ex| {
             if ext.eq(ex) {
                 let todo_file_name = format!("TODO.{}", ex);
                 all_files.push(file_parent_path.join(Path::new(todo_file_name.as_str())));
@@ -63,14 +260,15 @@ fn get_locales_files_path<S: AsRef<Path>>(files: &[S]) -> Vec<PathBuf> {
 /// Convert serde Value to the correct format
 fn convert_serde_to_string(value: &serde_json::Value, format: &str) -> String {
     match format {
-        "json" => serde_json::to_string_pretty(&value).unwrap(),
+        "json" | "json5" => serde_json::to_string_pretty(&value).unwrap(),
         "yaml" | "yml" => {
             let text = serde_yaml::to_string(&value).unwrap();
             // Remove leading `---`
             text.trim_start_matches("---").trim_start().to_string()
         }
         "toml" => toml::to_string_pretty(&value).unwrap(),
-        _ => unreachable!(),
+        // Unknown or missing extension: JSON is the sane, lossless default.
+        _ => serde_json::to_string_pretty(&value).unwrap(),
     }
 }
 
@@ -136,6 +334,59 @@ mod tests {
         assert_vec_eq!(paths, one_arg);
     }
 
+    #[test]
+    fn test_parse_glob_pattern_to_path() {
+        let tmp_dir = std::env::temp_dir().join("rust_i18n_glob_test");
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+
+        let en_file = tmp_dir.join("en.yml");
+        let zh_file = tmp_dir.join("zh-CN.yml");
+        let ignored_file = tmp_dir.join("notes.txt");
+
+        write_file!("_version: 2", &en_file);
+        write_file!("_version: 2", &zh_file);
+        write_file!("not a locale file", &ignored_file);
+
+        let pattern = tmp_dir.join("*.yml").to_str().unwrap().to_string();
+        let paths = get_locales_files_path(&[pattern.as_str()]);
+
+        assert_eq!(paths.len(), 2, "Only the two yml files should match");
+        assert_vec_eq!(paths, [en_file.to_str().unwrap(), zh_file.to_str().unwrap()]);
+    }
+
+    #[test]
+    fn test_parse_brace_glob_pattern_to_path() {
+        let tmp_dir = std::env::temp_dir().join("rust_i18n_brace_glob_test");
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+
+        let yml_file = tmp_dir.join("en.yml");
+        let json_file = tmp_dir.join("zh-CN.json");
+        let toml_file = tmp_dir.join("fr.toml");
+        let ignored_file = tmp_dir.join("notes.txt");
+
+        write_file!("_version: 2", &yml_file);
+        write_file!("{}", &json_file);
+        write_file!("_version = 2", &toml_file);
+        write_file!("not a locale file", &ignored_file);
+
+        let pattern = tmp_dir
+            .join("*.{yml,json,toml}")
+            .to_str()
+            .unwrap()
+            .to_string();
+        let paths = get_locales_files_path(&[pattern.as_str()]);
+
+        assert_eq!(paths.len(), 3, "All three supported extensions should match");
+        assert_vec_eq!(
+            paths,
+            [
+                yml_file.to_str().unwrap(),
+                json_file.to_str().unwrap(),
+                toml_file.to_str().unwrap()
+            ]
+        );
+    }
+
     #[test]
     fn test_cli_merge_todo_file() {
         let todo_yaml_str: &str = r#"_version: 2
@@ -158,7 +409,8 @@ _version: 2
         write_file!(todo_yaml_str, &todo_file);
         write_file!(translation_yaml_str, &yaml_file);
 
-        write_to_file(&get_merged_string(&[&yaml_file]), &yaml_file);
+        let (merged, _) = get_merged_string(&[&yaml_file], false, None).unwrap();
+        write_to_file(&merged, &yaml_file);
 
         let output = open_file_to_string(&yaml_file);
 
@@ -209,11 +461,134 @@ zh-CN = "甲乙丙丁""#;
         write_file!(yaml_str, &yaml_file);
         write_file!(toml_str, &toml_file);
 
-        write_to_file(
-            &get_merged_string(&[&json_file, &toml_file, &yaml_file]),
-            &yaml_file,
+        let (merged, _) = get_merged_string(&[&json_file, &toml_file, &yaml_file], false, None).unwrap();
+        write_to_file(&merged, &yaml_file);
+
+        let output = open_file_to_string(&yaml_file);
+
+        let output_value = serde_yaml::from_str::<serde_yaml::Value>(&output).unwrap();
+        let expected_value = serde_yaml::from_str::<serde_yaml::Value>(expected_yaml_str).unwrap();
+
+        assert_eq!(output_value, expected_value);
+    }
+
+    #[test]
+    fn test_strict_merge_rejects_conflicting_values() {
+        let a_str: &str = r#"_version: 2
+greeting:
+  en: hello"#;
+        let b_str: &str = r#"_version: 2
+greeting:
+  en: hi"#;
+
+        let tmp_dir = std::env::temp_dir();
+        let a_file = tmp_dir.clone().join("strict_a.yaml");
+        let b_file = tmp_dir.clone().join("strict_b.yaml");
+
+        write_file!(a_str, &a_file);
+        write_file!(b_str, &b_file);
+
+        let err = get_merged_string(&[&a_file, &b_file], true, None).unwrap_err();
+        assert_eq!(err.pointer, "/greeting/en");
+    }
+
+    #[test]
+    fn test_strict_merge_allows_identical_values() {
+        let a_str: &str = r#"_version: 2
+greeting:
+  en: hello"#;
+        let b_str: &str = r#"_version: 2
+greeting:
+  en: hello"#;
+
+        let tmp_dir = std::env::temp_dir();
+        let a_file = tmp_dir.clone().join("strict_same_a.yaml");
+        let b_file = tmp_dir.clone().join("strict_same_b.yaml");
+
+        write_file!(a_str, &a_file);
+        write_file!(b_str, &b_file);
+
+        let (merged, _) = get_merged_string(&[&a_file, &b_file], true, None).unwrap();
+        assert_eq!(merged["greeting"]["en"], "hello");
+    }
+
+    #[test]
+    fn test_merge_from_manifest_file() {
+        let a_str: &str = r#"_version: 2
+一二三四:
+  en: one two three four"#;
+        let b_str: &str = r#"_version: 2
+一二三四:
+  zh-CN: 一二三四"#;
+
+        let tmp_dir = std::env::temp_dir();
+        let a_file = tmp_dir.clone().join("fromfile_a.yaml");
+        let b_file = tmp_dir.clone().join("fromfile_b.yaml");
+        let manifest_file = tmp_dir.clone().join("fromfile_manifest.txt");
+
+        write_file!(a_str, &a_file);
+        write_file!(b_str, &b_file);
+        write_file!(
+            format!("{}\n\n{}\n", a_file.to_str().unwrap(), b_file.to_str().unwrap()),
+            &manifest_file
         );
 
+        let (merged, read_paths) =
+            get_merged_string::<&str>(&[], false, Some(&manifest_file)).unwrap();
+
+        assert_eq!(merged["一二三四"]["en"], "one two three four");
+        assert_eq!(merged["一二三四"]["zh-CN"], "一二三四");
+        assert_eq!(read_paths.len(), 2, "both manifest entries were read");
+    }
+
+    #[test]
+    fn test_write_depfile() {
+        let tmp_dir = std::env::temp_dir();
+        let depfile = tmp_dir.clone().join("merge.d");
+        let target = tmp_dir.clone().join("en.yml");
+        let inputs = vec![tmp_dir.clone().join("a.yml"), tmp_dir.clone().join("b.yml")];
+
+        write_depfile(&depfile, &target, &inputs);
+
+        let content = open_file_to_string(&depfile);
+        assert_eq!(
+            content.trim(),
+            format!(
+                "{}: {} {}",
+                target.to_str().unwrap(),
+                inputs[0].to_str().unwrap(),
+                inputs[1].to_str().unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_cli_merge_json5_file() {
+        // JSON5 tolerates comments and trailing commas.
+        let json5_str: &str = r#"{
+  // greeting
+  greeting: {
+    en: "hello",
+  },
+}"#;
+        let yaml_str: &str = r#"greeting:
+  zh-CN: 你好"#;
+
+        let expected_yaml_str: &str = r#"greeting:
+  en: hello
+  zh-CN: 你好"#;
+
+        let tmp_dir = std::env::temp_dir();
+
+        let json5_file = tmp_dir.clone().join("json5_file.json5");
+        let yaml_file = tmp_dir.clone().join("json5_merge.yaml");
+
+        write_file!(json5_str, &json5_file);
+        write_file!(yaml_str, &yaml_file);
+
+        let (merged, _) = get_merged_string(&[&json5_file, &yaml_file], false, None).unwrap();
+        write_to_file(&merged, &yaml_file);
+
         let output = open_file_to_string(&yaml_file);
 
         let output_value = serde_yaml::from_str::<serde_yaml::Value>(&output).unwrap();
@@ -221,4 +596,65 @@ zh-CN = "甲乙丙丁""#;
 
         assert_eq!(output_value, expected_value);
     }
+
+    #[test]
+    fn test_merge_detects_format_of_mis_extensioned_file() {
+        // A JSON file misnamed with a `.yml` extension must still parse.
+        let mis_named_str: &str = r#"{"greeting": {"en": "hello"}}"#;
+        let extensionless_str: &str = r#"greeting:
+  zh-CN: 你好"#;
+
+        let tmp_dir = std::env::temp_dir();
+        let mis_named_file = tmp_dir.clone().join("mis_named.yml");
+        let extensionless_file = tmp_dir.clone().join("extensionless_locale");
+
+        write_file!(mis_named_str, &mis_named_file);
+        write_file!(extensionless_str, &extensionless_file);
+
+        let (merged, _) =
+            get_merged_string(&[&mis_named_file, &extensionless_file], false, None).unwrap();
+
+        assert_eq!(merged["greeting"]["en"], "hello");
+        assert_eq!(merged["greeting"]["zh-CN"], "你好");
+    }
+
+    #[test]
+    fn test_merge_detects_flat_toml_without_misreading_as_yaml() {
+        // A flat TOML line like `en = "hello"` also parses as a bare YAML
+        // scalar string, so detection must not stop at YAML here.
+        let toml_str: &str = r#"en = "hello""#;
+
+        let tmp_dir = std::env::temp_dir();
+        let extensionless_file = tmp_dir.clone().join("extensionless_flat_toml");
+
+        write_file!(toml_str, &extensionless_file);
+
+        let (merged, _) = get_merged_string(&[&extensionless_file], false, None).unwrap();
+
+        assert_eq!(merged["en"], "hello");
+    }
+
+    #[test]
+    fn test_merge_unions_arrays_without_duplicates() {
+        let a_str: &str = r#"options:
+  - one
+  - two"#;
+        let b_str: &str = r#"options:
+  - two
+  - three"#;
+
+        let tmp_dir = std::env::temp_dir();
+        let a_file = tmp_dir.clone().join("array_a.yaml");
+        let b_file = tmp_dir.clone().join("array_b.yaml");
+
+        write_file!(a_str, &a_file);
+        write_file!(b_str, &b_file);
+
+        let (merged, _) = get_merged_string(&[&a_file, &b_file], false, None).unwrap();
+
+        assert_eq!(
+            merged["options"],
+            serde_json::json!(["one", "two", "three"])
+        );
+    }
 }